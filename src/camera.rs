@@ -0,0 +1,80 @@
+use rltk::{Rltk, RGB};
+use specs::prelude::*;
+
+use crate::components::{Position, Viewshed};
+use crate::map::{Map, TileType};
+use crate::player::Player;
+
+pub struct CameraBounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+pub fn get_camera_bounds(world: &World, context: &Rltk) -> CameraBounds {
+    let positions = world.read_storage::<Position>();
+    let players = world.read_storage::<Player>();
+    let player_position = (&players, &positions)
+        .join()
+        .map(|(_player, position)| *position)
+        .next()
+        .expect("no player entity with a Position found");
+
+    let (x_chars, y_chars) = context.get_char_size();
+    let center_x = x_chars as i32 / 2;
+    let center_y = y_chars as i32 / 2;
+
+    let min_x = player_position.x - center_x;
+    let min_y = player_position.y - center_y;
+
+    CameraBounds {
+        min_x,
+        max_x: min_x + x_chars as i32,
+        min_y,
+        max_y: min_y + y_chars as i32,
+    }
+}
+
+pub fn render_camera(world: &World, map: &Map, context: &mut Rltk) {
+    let bounds = get_camera_bounds(world, context);
+    let mut viewsheds = world.write_storage::<Viewshed>();
+    let players = world.read_storage::<Player>();
+
+    for (_player, viewshed) in (&players, &mut viewsheds).join() {
+        for ty in bounds.min_y..bounds.max_y {
+            for tx in bounds.min_x..bounds.max_x {
+                let screen_x = tx - bounds.min_x;
+                let screen_y = ty - bounds.min_y;
+
+                if !map.is_in_bound(tx, ty) {
+                    context.set(screen_x, screen_y, RGB::from_f32(0.2, 0.2, 0.2), RGB::from_f32(0., 0., 0.), rltk::to_cp437('·'));
+                    continue;
+                }
+
+                let index = map.index_of(tx, ty);
+                if !viewshed.revealed_tiles[index] {
+                    continue;
+                }
+
+                let tile = map.tiles[index];
+                let glyph = match tile {
+                    TileType::Floor => rltk::to_cp437('.'),
+                    TileType::Wall => rltk::to_cp437('#'),
+                    TileType::DownStairs => rltk::to_cp437('>'),
+                };
+                let mut foreground = match tile {
+                    TileType::Floor => RGB::from_f32(0.0, 0.5, 0.5),
+                    TileType::Wall => RGB::from_f32(0., 1.0, 0.),
+                    TileType::DownStairs => RGB::from_f32(0., 1.0, 1.0),
+                };
+
+                if !viewshed.visible_tiles[index] {
+                    foreground = foreground.to_greyscale();
+                }
+
+                context.set(screen_x, screen_y, foreground, RGB::from_f32(0., 0., 0.), glyph);
+            }
+        }
+    }
+}
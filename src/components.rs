@@ -0,0 +1,71 @@
+use rltk::{FontCharType, RGB};
+use serde::{Deserialize, Serialize};
+use specs::error::NoError;
+use specs::prelude::*;
+use specs::saveload::{ConvertSaveload, Marker};
+use specs_derive::{Component, ConvertSaveload};
+
+#[derive(Component, ConvertSaveload, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Renderable {
+    pub glyph: FontCharType,
+    pub foreground: RGB,
+    pub background: RGB,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Viewshed {
+    pub visible_tiles: Vec<bool>,
+    pub revealed_tiles: Vec<bool>,
+    pub range: i32,
+    pub dirty: bool,
+}
+
+impl Viewshed {
+    pub fn new(range: i32, tile_count: usize) -> Viewshed {
+        Viewshed {
+            visible_tiles: vec![false; tile_count],
+            revealed_tiles: vec![false; tile_count],
+            range,
+            dirty: true,
+        }
+    }
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Monster {}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct CombatStats {
+    pub max_hp: i32,
+    pub hp: i32,
+    pub defense: i32,
+    pub power: i32,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct WantsToMelee {
+    pub target: Entity,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct SufferDamage {
+    pub amount: Vec<i32>,
+}
+
+impl SufferDamage {
+    pub fn new_damage(store: &mut WriteStorage<SufferDamage>, victim: Entity, amount: i32) {
+        if let Some(suffering) = store.get_mut(victim) {
+            suffering.amount.push(amount);
+        } else {
+            store
+                .insert(victim, SufferDamage { amount: vec![amount] })
+                .expect("failed to insert SufferDamage");
+        }
+    }
+}
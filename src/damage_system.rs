@@ -0,0 +1,34 @@
+use specs::prelude::*;
+
+use crate::components::{CombatStats, SufferDamage};
+
+pub struct DamageSystem {}
+
+impl<'a> System<'a> for DamageSystem {
+    type SystemData = (WriteStorage<'a, CombatStats>, WriteStorage<'a, SufferDamage>);
+
+    fn run(&mut self, (mut combat_stats, mut suffer_damage): Self::SystemData) {
+        for (stats, damage) in (&mut combat_stats, &suffer_damage).join() {
+            stats.hp -= damage.amount.iter().sum::<i32>();
+        }
+
+        suffer_damage.clear();
+    }
+}
+
+pub fn delete_the_dead(world: &mut World) {
+    let dead: Vec<Entity> = {
+        let combat_stats = world.read_storage::<CombatStats>();
+        let entities = world.entities();
+
+        (&entities, &combat_stats)
+            .join()
+            .filter(|(_entity, stats)| stats.hp <= 0)
+            .map(|(entity, _stats)| entity)
+            .collect()
+    };
+
+    for victim in dead {
+        world.delete_entity(victim).expect("failed to delete dead entity");
+    }
+}
@@ -1,36 +1,289 @@
 #[forbid(warnings)]
 
-use rltk::{FontCharType, GameState, Rltk, RltkBuilder, RGB};
+use std::fs::File;
+
+use rltk::{GameState, Rltk, RltkBuilder, VirtualKeyCode, RGB};
 use specs::prelude::*;
-use specs_derive::Component;
+use specs::saveload::{MarkedBuilder, SimpleMarker, SimpleMarkerAllocator};
 
-#[derive(Component)]
-struct Position {
-    x: i32,
-    y: i32,
-}
+mod camera;
+mod components;
+mod damage_system;
+mod map;
+mod map_indexing_system;
+mod melee_combat_system;
+mod monster_ai_system;
+mod player;
+mod spawner;
+mod visibility_system;
+
+use components::{CombatStats, Monster, Position, Renderable, Viewshed};
+use map::{Map, TileType};
+use player::{try_move_player, Player};
 
-#[derive(Component)]
-struct Renderable {
-    glyph: FontCharType,
-    foreground: RGB,
-    background: RGB,
+#[derive(PartialEq, Copy, Clone)]
+pub enum RunState {
+    Paused,
+    Running,
 }
 
+const SAVE_PATH: &str = "savegame.json";
+
+pub struct SerializeMe;
+
 struct State {
     world: World,
 }
 
-impl GameState for State {
+impl State {
+    fn run_systems(&mut self) {
+        let mut map_indexing = map_indexing_system::MapIndexingSystem {};
+        map_indexing.run_now(&self.world);
+
+        let mut monster_ai = monster_ai_system::MonsterAISystem {};
+        monster_ai.run_now(&self.world);
+
+        let mut melee_combat = melee_combat_system::MeleeCombatSystem {};
+        melee_combat.run_now(&self.world);
+
+        let mut damage = damage_system::DamageSystem {};
+        damage.run_now(&self.world);
+
+        damage_system::delete_the_dead(&mut self.world);
+
+        let mut map_reindexing = map_indexing_system::MapIndexingSystem {};
+        map_reindexing.run_now(&self.world);
+
+        let mut visibility = visibility_system::VisibilitySystem {};
+        visibility.run_now(&self.world);
+
+        self.world.maintain();
+    }
+
+    fn handle_input(&mut self, context: &mut Rltk) -> RunState {
+        match context.key {
+            None => RunState::Paused,
+            Some(key) => match key {
+                VirtualKeyCode::Left | VirtualKeyCode::H => {
+                    try_move_player(-1, 0, &self.world);
+                    RunState::Running
+                }
+                VirtualKeyCode::Right | VirtualKeyCode::L => {
+                    try_move_player(1, 0, &self.world);
+                    RunState::Running
+                }
+                VirtualKeyCode::Up | VirtualKeyCode::K => {
+                    try_move_player(0, -1, &self.world);
+                    RunState::Running
+                }
+                VirtualKeyCode::Down | VirtualKeyCode::J => {
+                    try_move_player(0, 1, &self.world);
+                    RunState::Running
+                }
+                VirtualKeyCode::S => {
+                    self.save_game();
+                    RunState::Paused
+                }
+                VirtualKeyCode::O => {
+                    self.load_game();
+                    RunState::Paused
+                }
+                VirtualKeyCode::Period => {
+                    if self.try_goto_next_level() {
+                        RunState::Running
+                    } else {
+                        RunState::Paused
+                    }
+                }
+                _ => RunState::Paused,
+            },
+        }
+    }
+
+    fn try_goto_next_level(&mut self) -> bool {
+        let player_position = {
+            let positions = self.world.read_storage::<Position>();
+            let players = self.world.read_storage::<Player>();
+
+            (&players, &positions).join().map(|(_player, position)| *position).next()
+        };
+
+        match player_position {
+            Some(position) => {
+                let on_down_stairs = {
+                    let map = self.world.fetch::<Map>();
+                    let index = map.index_of(position.x, position.y);
+                    map.tiles[index] == TileType::DownStairs
+                };
+
+                if on_down_stairs {
+                    self.goto_next_level();
+                }
+
+                on_down_stairs
+            }
+            None => false,
+        }
+    }
+
+    fn goto_next_level(&mut self) {
+        let (width, height, depth) = {
+            let map = self.world.fetch::<Map>();
+            (map.width, map.height, map.depth)
+        };
+
+        let new_map = Map::new(width, height, depth + 1);
+        let new_starting_position = new_map.starting_position();
+        let tile_count = (new_map.width * new_map.height) as usize;
+
+        let mut positions = self.world.write_storage::<Position>();
+        let players = self.world.read_storage::<Player>();
+
+        for (_player, position) in (&players, &mut positions).join() {
+            position.x = new_starting_position.x;
+            position.y = new_starting_position.y;
+        }
+
+        drop(positions);
+        drop(players);
+
+        let mut viewsheds = self.world.write_storage::<Viewshed>();
+
+        for viewshed in (&mut viewsheds).join() {
+            viewshed.visible_tiles = vec![false; tile_count];
+            viewshed.revealed_tiles = vec![false; tile_count];
+            viewshed.dirty = true;
+        }
 
+        drop(viewsheds);
+
+        let previous_floor_monsters: Vec<Entity> = {
+            let entities = self.world.entities();
+            let monsters = self.world.read_storage::<Monster>();
+
+            (&entities, &monsters).join().map(|(entity, _monster)| entity).collect()
+        };
+
+        for monster in previous_floor_monsters {
+            self.world.delete_entity(monster).expect("failed to delete monster");
+        }
+
+        spawner::spawn_monsters(&mut self.world, &new_map);
+        self.world.insert(new_map);
+    }
+
+    fn save_game(&self) {
+        let map = self.world.fetch::<Map>().clone();
+        let writer = File::create(SAVE_PATH).unwrap();
+        let mut serializer = serde_json::Serializer::new(writer);
+
+        specs::saveload::SerializeComponents::<std::convert::Infallible, SimpleMarker<SerializeMe>>::serialize(
+            &(
+                self.world.read_storage::<Position>(),
+                self.world.read_storage::<Renderable>(),
+                self.world.read_storage::<Viewshed>(),
+                self.world.read_storage::<Player>(),
+                self.world.read_storage::<CombatStats>(),
+                self.world.read_storage::<Monster>(),
+            ),
+            &self.world.entities(),
+            &self.world.read_storage::<SimpleMarker<SerializeMe>>(),
+            &mut serializer,
+        )
+        .expect("failed to serialize world");
+
+        serde_json::to_writer(
+            &mut File::create(format!("{}.map", SAVE_PATH)).unwrap(),
+            &map,
+        )
+        .expect("failed to serialize map");
+    }
+
+    fn load_game(&mut self) {
+        let (map_file, world_file) = match (File::open(format!("{}.map", SAVE_PATH)), File::open(SAVE_PATH)) {
+            (Ok(map_file), Ok(world_file)) => (map_file, world_file),
+            _ => return,
+        };
+
+        self.world.delete_all();
+
+        let mut map: Map = serde_json::from_reader(map_file).expect("failed to deserialize map");
+        let mut deserializer = serde_json::Deserializer::from_reader(world_file);
+
+        specs::saveload::DeserializeComponents::<std::convert::Infallible, _>::deserialize(
+            &mut (
+                self.world.write_storage::<Position>(),
+                self.world.write_storage::<Renderable>(),
+                self.world.write_storage::<Viewshed>(),
+                self.world.write_storage::<Player>(),
+                self.world.write_storage::<CombatStats>(),
+                self.world.write_storage::<Monster>(),
+            ),
+            &self.world.entities(),
+            &mut self.world.write_storage::<SimpleMarker<SerializeMe>>(),
+            &mut self.world.write_resource::<SimpleMarkerAllocator<SerializeMe>>(),
+            &mut deserializer,
+        )
+        .expect("failed to deserialize world");
+
+        map.clear_entities();
+        map.update_blocked_tiles();
+        self.world.insert(map);
+    }
+}
+
+impl GameState for State {
     fn tick(&mut self, context: &mut Rltk) {
         context.cls();
 
-        let positions = self.world.read_storage::<Position>();
-        let renderables = self.world.read_storage::<Renderable>();
+        let run_state = self.handle_input(context);
+        self.world.insert(run_state);
+
+        if run_state == RunState::Running {
+            self.run_systems();
+        }
+
+        let player_is_alive = (&self.world.read_storage::<Player>()).join().next().is_some();
+        if !player_is_alive {
+            context.quit();
+            return;
+        }
+
+        let map = self.world.fetch::<Map>();
+        camera::render_camera(&self.world, &map, context);
+        let bounds = camera::get_camera_bounds(&self.world, context);
 
-        for (position, renderable) in (&positions, &renderables).join() {
-            context.set(position.x, position.y, renderable.foreground, renderable.background, renderable.glyph);
+        let viewsheds = self.world.read_storage::<Viewshed>();
+        let players = self.world.read_storage::<Player>();
+        let player_viewshed = (&players, &viewsheds).join().map(|(_player, viewshed)| viewshed).next();
+
+        if let Some(player_viewshed) = player_viewshed {
+            let positions = self.world.read_storage::<Position>();
+            let renderables = self.world.read_storage::<Renderable>();
+
+            for (position, renderable) in (&positions, &renderables).join() {
+                if !map.is_in_bound(position.x, position.y) {
+                    continue;
+                }
+
+                let index = map.index_of(position.x, position.y);
+                if !player_viewshed.revealed_tiles[index] {
+                    continue;
+                }
+
+                let screen_x = position.x - bounds.min_x;
+                let screen_y = position.y - bounds.min_y;
+
+                if screen_x >= 0 && screen_x < bounds.max_x - bounds.min_x && screen_y >= 0 && screen_y < bounds.max_y - bounds.min_y {
+                    let foreground = if player_viewshed.visible_tiles[index] {
+                        renderable.foreground
+                    } else {
+                        renderable.foreground.to_greyscale()
+                    };
+
+                    context.set(screen_x, screen_y, foreground, renderable.background, renderable.glyph);
+                }
+            }
         }
     }
 }
@@ -45,17 +298,36 @@ fn main() -> rltk::BError {
 
     state.world.register::<Position>();
     state.world.register::<Renderable>();
+    state.world.register::<Viewshed>();
+    state.world.register::<Player>();
+    state.world.register::<CombatStats>();
+    state.world.register::<Monster>();
+    state.world.register::<components::WantsToMelee>();
+    state.world.register::<components::SufferDamage>();
+    state.world.register::<SimpleMarker<SerializeMe>>();
+    state.world.insert(SimpleMarkerAllocator::<SerializeMe>::new());
+    state.world.insert(RunState::Paused);
+
+    let map = Map::new(160, 100, 1);
+    let starting_position = map.starting_position();
 
     state
         .world
         .create_entity()
-        .with(Position { x: 40, y: 25 })
+        .with(Position { x: starting_position.x, y: starting_position.y })
         .with(Renderable {
             glyph: rltk::to_cp437('@'),
             foreground: RGB::named(rltk::YELLOW),
             background: RGB::named(rltk::BLACK),
         })
+        .with(Viewshed::new(8, (map.width * map.height) as usize))
+        .with(Player {})
+        .with(CombatStats { max_hp: 30, hp: 30, defense: 2, power: 5 })
+        .marked::<SimpleMarker<SerializeMe>>()
         .build();
 
+    spawner::spawn_monsters(&mut state.world, &map);
+    state.world.insert(map);
+
     rltk::main_loop(context, state)
 }
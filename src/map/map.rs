@@ -1,47 +1,51 @@
 use std::cmp::{max, min};
 
-use rltk::{Algorithm2D, BaseMap, Point, RandomNumberGenerator, RGB, Rltk, SmallVec};
+use rltk::{Algorithm2D, BaseMap, Point, RandomNumberGenerator, SmallVec};
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
 use crate::components::Position;
 use crate::map::room::Room;
-use crate::player::Player;
-use crate::Viewshed;
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum TileType {
     Wall,
     Floor,
+    DownStairs,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Map {
     pub width: i32,
     pub height: i32,
+    pub depth: i32,
     pub tiles: Vec<TileType>,
     pub rooms: Vec<Room>,
     pub blocked_tiles: Vec<bool>,
+    #[serde(skip)]
     pub entities: Vec<Vec<Entity>>,
 }
 
 impl Map {
-    pub fn new(width: i32, height: i32) -> Map {
+    pub fn new(width: i32, height: i32, depth: i32) -> Map {
         let map_size = (width * height) as usize;
         let mut map = Map {
             width,
             height,
+            depth,
             tiles: vec![TileType::Wall; map_size],
             rooms: Vec::new(),
             blocked_tiles: vec![true; map_size],
             entities: vec![Vec::new(); map_size],
         };
 
-        const MAX_ROOMS: i32 = 30;
         const MIN_SIZE: i32 = 6;
         const MAX_SIZE: i32 = 10;
 
+        let max_rooms = 30 + depth * 2;
         let mut rng = RandomNumberGenerator::new();
 
-        for _ in 0..MAX_ROOMS {
+        for _ in 0..max_rooms {
             let room_width = rng.range(MIN_SIZE, MAX_SIZE);
             let room_height = rng.range(MIN_SIZE, MAX_SIZE);
             let x = rng.roll_dice(1, width - room_width - 1) - 1;
@@ -64,6 +68,12 @@ impl Map {
             }
         }
 
+        if let Some(last_room) = map.rooms.last() {
+            let down_stairs_center = last_room.center();
+            let index = map.index_of(down_stairs_center.x, down_stairs_center.y);
+            map.tiles[index] = TileType::DownStairs;
+        }
+
         map
     }
 
@@ -153,42 +163,8 @@ impl Map {
     }
 
     pub fn clear_entities(&mut self) {
-        self.entities.iter_mut().for_each(|entities| entities.clear());
-    }
-
-    pub fn draw(&self, world: &World, context: &mut Rltk) {
-        let mut players = world.write_storage::<Player>();
-        let mut viewsheds = world.write_storage::<Viewshed>();
-
-        for (_player, viewshed) in (&mut players, &mut viewsheds).join() {
-            let mut y = 0;
-            let mut x = 0;
-
-            for (index, tile) in self.tiles.iter().enumerate() {
-                if viewshed.revealed_tiles[index] {
-                    let glyph = match tile {
-                        TileType::Floor => rltk::to_cp437('.'),
-                        TileType::Wall => rltk::to_cp437('#'),
-                    };
-                    let mut foreground = match tile {
-                        TileType::Floor => RGB::from_f32(0.0, 0.5, 0.5),
-                        TileType::Wall => RGB::from_f32(0., 1.0, 0.),
-                    };
-
-                    if !viewshed.visible_tiles[index] {
-                        foreground = foreground.to_greyscale();
-                    }
-
-                    context.set(x, y, foreground, RGB::from_f32(0., 0., 0.), glyph);
-                }
-
-                x += 1;
-                if x > self.width - 1 {
-                    x = 0;
-                    y += 1;
-                }
-            }
-        }
+        let map_size = (self.width * self.height) as usize;
+        self.entities = vec![Vec::new(); map_size];
     }
 }
 
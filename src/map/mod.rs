@@ -0,0 +1,5 @@
+mod map;
+mod room;
+
+pub use map::{Map, TileType};
+pub use room::Room;
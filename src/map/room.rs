@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::components::Position;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Room {
+    pub first: Position,
+    pub second: Position,
+}
+
+impl Room {
+    pub fn new(first: Position, width: i32, height: i32) -> Room {
+        let second = Position { x: first.x + width, y: first.y + height };
+
+        Room { first, second }
+    }
+
+    pub fn intersect(&self, other: &Room) -> bool {
+        self.first.x <= other.second.x
+            && self.second.x >= other.first.x
+            && self.first.y <= other.second.y
+            && self.second.y >= other.first.y
+    }
+
+    pub fn center(&self) -> Position {
+        Position {
+            x: (self.first.x + self.second.x) / 2,
+            y: (self.first.y + self.second.y) / 2,
+        }
+    }
+}
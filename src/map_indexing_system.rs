@@ -0,0 +1,29 @@
+use specs::prelude::*;
+
+use crate::components::{Monster, Position};
+use crate::map::Map;
+
+pub struct MapIndexingSystem {}
+
+impl<'a> System<'a> for MapIndexingSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Monster>,
+        Entities<'a>,
+    );
+
+    fn run(&mut self, (mut map, positions, monsters, entities): Self::SystemData) {
+        map.clear_entities();
+        map.update_blocked_tiles();
+
+        for (entity, position) in (&entities, &positions).join() {
+            let index = map.index_of(position.x, position.y);
+            map.entities[index].push(entity);
+
+            if monsters.get(entity).is_some() {
+                map.blocked_tiles[index] = true;
+            }
+        }
+    }
+}
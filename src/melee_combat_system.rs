@@ -0,0 +1,36 @@
+use specs::prelude::*;
+
+use crate::components::{CombatStats, SufferDamage, WantsToMelee};
+
+pub struct MeleeCombatSystem {}
+
+impl<'a> System<'a> for MeleeCombatSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, WantsToMelee>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+    );
+
+    fn run(&mut self, (entities, mut wants_to_melee, combat_stats, mut suffer_damage): Self::SystemData) {
+        for (_attacker, wants_to_melee, stats) in (&entities, &wants_to_melee, &combat_stats).join() {
+            if stats.hp <= 0 {
+                continue;
+            }
+
+            if let Some(target_stats) = combat_stats.get(wants_to_melee.target) {
+                if target_stats.hp <= 0 {
+                    continue;
+                }
+
+                let damage = i32::max(0, stats.power - target_stats.defense);
+
+                if damage > 0 {
+                    SufferDamage::new_damage(&mut suffer_damage, wants_to_melee.target, damage);
+                }
+            }
+        }
+
+        wants_to_melee.clear();
+    }
+}
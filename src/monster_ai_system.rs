@@ -0,0 +1,71 @@
+use rltk::{a_star_search, DistanceAlg, Point};
+use specs::prelude::*;
+
+use crate::components::{Monster, Position, Viewshed, WantsToMelee};
+use crate::map::Map;
+use crate::player::Player;
+use crate::RunState;
+
+pub struct MonsterAISystem {}
+
+impl<'a> System<'a> for MonsterAISystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadExpect<'a, RunState>,
+        Entities<'a>,
+        ReadStorage<'a, Monster>,
+        ReadStorage<'a, Player>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, WantsToMelee>,
+    );
+
+    fn run(&mut self, (mut map, run_state, entities, monsters, players, mut viewsheds, mut positions, mut wants_to_melee): Self::SystemData) {
+        if *run_state != RunState::Running {
+            return;
+        }
+
+        let player_data = (&entities, &players, &positions)
+            .join()
+            .map(|(entity, _player, position)| (entity, *position))
+            .next();
+
+        let (player_entity, player_position) = match player_data {
+            Some(data) => data,
+            None => return,
+        };
+
+        let player_index = map.index_of(player_position.x, player_position.y);
+
+        for (monster_entity, _monster, viewshed, position) in (&entities, &monsters, &mut viewsheds, &mut positions).join() {
+            if !viewshed.visible_tiles[player_index] {
+                continue;
+            }
+
+            let distance_to_player = DistanceAlg::Pythagoras
+                .distance2d(Point::new(position.x, position.y), Point::new(player_position.x, player_position.y));
+
+            if distance_to_player < 1.5 {
+                wants_to_melee
+                    .insert(monster_entity, WantsToMelee { target: player_entity })
+                    .expect("failed to insert WantsToMelee");
+                continue;
+            }
+
+            let monster_index = map.index_of(position.x, position.y);
+            let path = a_star_search(monster_index, player_index, &*map);
+
+            if path.success && path.steps.len() > 1 {
+                let destination_index = path.steps[1];
+                let (destination_x, destination_y) = map.position_of(destination_index);
+
+                map.blocked_tiles[monster_index] = false;
+                map.blocked_tiles[destination_index] = true;
+
+                position.x = destination_x;
+                position.y = destination_y;
+                viewshed.dirty = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use specs::error::NoError;
+use specs::prelude::*;
+use specs::saveload::{ConvertSaveload, Marker};
+use specs_derive::{Component, ConvertSaveload};
+
+use crate::components::{CombatStats, Position, Viewshed, WantsToMelee};
+use crate::map::Map;
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Player {}
+
+pub fn try_move_player(delta_x: i32, delta_y: i32, world: &World) {
+    let entities = world.entities();
+    let mut positions = world.write_storage::<Position>();
+    let mut viewsheds = world.write_storage::<Viewshed>();
+    let players = world.read_storage::<Player>();
+    let combat_stats = world.read_storage::<CombatStats>();
+    let mut wants_to_melee = world.write_storage::<WantsToMelee>();
+    let map = world.fetch::<Map>();
+
+    for (entity, _player, position, viewshed) in (&entities, &players, &mut positions, &mut viewsheds).join() {
+        let destination_x = position.x + delta_x;
+        let destination_y = position.y + delta_y;
+
+        if !map.is_in_bound(destination_x, destination_y) {
+            continue;
+        }
+
+        let destination_index = map.index_of(destination_x, destination_y);
+        let target = map.entities[destination_index]
+            .iter()
+            .find(|&&occupant| combat_stats.get(occupant).is_some());
+
+        if let Some(&target) = target {
+            wants_to_melee
+                .insert(entity, WantsToMelee { target })
+                .expect("failed to insert WantsToMelee");
+            continue;
+        }
+
+        if map.blocked_tiles[destination_index] {
+            continue;
+        }
+
+        position.x = destination_x;
+        position.y = destination_y;
+        viewshed.dirty = true;
+    }
+}
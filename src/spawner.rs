@@ -0,0 +1,40 @@
+use rltk::{RandomNumberGenerator, RGB};
+use specs::prelude::*;
+use specs::saveload::{MarkedBuilder, SimpleMarker};
+
+use crate::components::{CombatStats, Monster, Position, Renderable, Viewshed};
+use crate::map::Map;
+use crate::SerializeMe;
+
+pub fn spawn_monsters(world: &mut World, map: &Map) {
+    let mut rng = RandomNumberGenerator::new();
+    let tile_count = (map.width * map.height) as usize;
+
+    for room in map.rooms.iter().skip(1) {
+        if rng.roll_dice(1, 3) != 1 {
+            continue;
+        }
+
+        let center = room.center();
+        let (glyph, power) = if rng.roll_dice(1, 2) == 1 {
+            (rltk::to_cp437('g'), 4)
+        } else {
+            (rltk::to_cp437('o'), 6)
+        };
+        let hit_points = 10 + map.depth * 2;
+
+        world
+            .create_entity()
+            .with(Position { x: center.x, y: center.y })
+            .with(Renderable {
+                glyph,
+                foreground: RGB::named(rltk::RED),
+                background: RGB::named(rltk::BLACK),
+            })
+            .with(Viewshed::new(8, tile_count))
+            .with(Monster {})
+            .with(CombatStats { max_hp: hit_points, hp: hit_points, defense: 1, power })
+            .marked::<SimpleMarker<SerializeMe>>()
+            .build();
+    }
+}
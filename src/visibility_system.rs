@@ -0,0 +1,34 @@
+use rltk::{field_of_view, Point};
+use specs::prelude::*;
+
+use crate::components::{Position, Viewshed};
+use crate::map::Map;
+
+pub struct VisibilitySystem {}
+
+impl<'a> System<'a> for VisibilitySystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, Position>,
+    );
+
+    fn run(&mut self, (mut map, mut viewsheds, positions): Self::SystemData) {
+        for (viewshed, position) in (&mut viewsheds, &positions).join() {
+            if !viewshed.dirty {
+                continue;
+            }
+            viewshed.dirty = false;
+
+            viewshed.visible_tiles.iter_mut().for_each(|visible| *visible = false);
+
+            for point in field_of_view(Point::new(position.x, position.y), viewshed.range, &*map) {
+                if map.is_in_bound(point.x, point.y) {
+                    let index = map.index_of(point.x, point.y);
+                    viewshed.visible_tiles[index] = true;
+                    viewshed.revealed_tiles[index] = true;
+                }
+            }
+        }
+    }
+}